@@ -0,0 +1,159 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::attr::{Attr, AttrValue};
+use dom::bindings::codegen::Bindings::HTMLTableCellElementBinding;
+use dom::bindings::codegen::Bindings::HTMLTableCellElementBinding::HTMLTableCellElementMethods;
+use dom::bindings::inheritance::Castable;
+use dom::bindings::js::{LayoutJS, Root};
+use dom::document::Document;
+use dom::element::{AttributeMutation, Element};
+use dom::htmlelement::HTMLElement;
+use dom::htmltableelement::{HTMLTableElement, PresentationalHint, PresentationalHintsSynthesizer};
+use dom::node::Node;
+use dom::virtualmethods::VirtualMethods;
+use std::cell::Cell;
+use std::cmp;
+use string_cache::Atom;
+use util::str::{self, DOMString};
+
+// https://html.spec.whatwg.org/multipage/#dom-tdth-colspan
+const DEFAULT_COLSPAN: u32 = 1;
+const MAX_COLSPAN: u32 = 1000;
+
+// https://html.spec.whatwg.org/multipage/#dom-tdth-rowspan
+const DEFAULT_ROWSPAN: u32 = 1;
+const MAX_ROWSPAN: u32 = 65534;
+
+#[dom_struct]
+pub struct HTMLTableCellElement {
+    htmlelement: HTMLElement,
+    colspan: Cell<u32>,
+    rowspan: Cell<u32>,
+}
+
+impl HTMLTableCellElement {
+    fn new_inherited(localName: Atom, prefix: Option<DOMString>, document: &Document)
+                     -> HTMLTableCellElement {
+        HTMLTableCellElement {
+            htmlelement: HTMLElement::new_inherited(localName, prefix, document),
+            colspan: Cell::new(DEFAULT_COLSPAN),
+            rowspan: Cell::new(DEFAULT_ROWSPAN),
+        }
+    }
+
+    #[allow(unrooted_must_root)]
+    pub fn new(localName: Atom, prefix: Option<DOMString>, document: &Document)
+               -> Root<HTMLTableCellElement> {
+        let element = HTMLTableCellElement::new_inherited(localName, prefix, document);
+        Node::reflect_node(box element, document, HTMLTableCellElementBinding::Wrap)
+    }
+}
+
+impl HTMLTableCellElementMethods for HTMLTableCellElement {
+    // https://html.spec.whatwg.org/multipage/#dom-tdth-colspan
+    fn ColSpan(&self) -> u32 {
+        self.colspan.get()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-tdth-colspan
+    fn SetColSpan(&self, value: u32) {
+        self.upcast::<Element>().set_uint_attribute(&atom!("colspan"), value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-tdth-rowspan
+    fn RowSpan(&self) -> u32 {
+        self.rowspan.get()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-tdth-rowspan
+    fn SetRowSpan(&self, value: u32) {
+        self.upcast::<Element>().set_uint_attribute(&atom!("rowspan"), value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-tdth-headers
+    make_getter!(Headers, "headers");
+
+    // https://html.spec.whatwg.org/multipage/#dom-tdth-headers
+    make_setter!(SetHeaders, "headers");
+}
+
+impl PresentationalHintsSynthesizer for HTMLTableCellElement {
+    fn collect_presentational_hints(&self, hints: &mut Vec<PresentationalHint>) {
+        // https://html.spec.whatwg.org/multipage/#attr-table-cellpadding
+        let cellpadding = self.upcast::<Node>()
+                               .ancestors()
+                               .filter_map(Root::downcast::<HTMLTableElement>)
+                               .next()
+                               .and_then(|table| table.upcast::<Element>().get_attr(&ns!(), &atom!("cellpadding")))
+                               .map(|padding| padding.value().as_dimension().clone());
+
+        if let Some(padding) = cellpadding {
+            hints.push(PresentationalHint::Padding(padding));
+        }
+    }
+}
+
+/// Meant to be consumed by the layout table builder when it allocates grid
+/// slots for spanning cells, so it can read colspan/rowspan off
+/// `LayoutJS` without touching the DOM thread. That builder lives in the
+/// layout crate, outside this slice of the tree, and is NOT wired up to
+/// these accessors yet — grid-slot allocation from colspan/rowspan remains
+/// open follow-up work, tracked separately from this DOM-reflection change.
+pub trait HTMLTableCellElementLayoutHelpers {
+    fn get_colspan(&self) -> u32;
+    fn get_rowspan(&self) -> u32;
+}
+
+impl HTMLTableCellElementLayoutHelpers for LayoutJS<HTMLTableCellElement> {
+    #[allow(unsafe_code)]
+    fn get_colspan(&self) -> u32 {
+        unsafe {
+            (*self.unsafe_get()).colspan.get()
+        }
+    }
+
+    #[allow(unsafe_code)]
+    fn get_rowspan(&self) -> u32 {
+        unsafe {
+            (*self.unsafe_get()).rowspan.get()
+        }
+    }
+}
+
+impl VirtualMethods for HTMLTableCellElement {
+    fn super_type(&self) -> Option<&VirtualMethods> {
+        Some(self.upcast::<HTMLElement>() as &VirtualMethods)
+    }
+
+    fn attribute_mutated(&self, attr: &Attr, mutation: AttributeMutation) {
+        self.super_type().unwrap().attribute_mutated(attr, mutation);
+        match *attr.local_name() {
+            atom!("colspan") => {
+                // https://html.spec.whatwg.org/multipage/#dom-tdth-colspan
+                let span = mutation.new_value(attr).and_then(|value| {
+                    str::parse_unsigned_integer(value.chars())
+                }).unwrap_or(DEFAULT_COLSPAN);
+                self.colspan.set(cmp::max(DEFAULT_COLSPAN, cmp::min(span, MAX_COLSPAN)));
+            },
+            atom!("rowspan") => {
+                // https://html.spec.whatwg.org/multipage/#dom-tdth-rowspan
+                let span = mutation.new_value(attr).and_then(|value| {
+                    str::parse_unsigned_integer(value.chars())
+                }).unwrap_or(DEFAULT_ROWSPAN);
+                self.rowspan.set(cmp::min(span, MAX_ROWSPAN));
+            },
+            _ => {},
+        }
+    }
+
+    fn parse_plain_attribute(&self, local_name: &Atom, value: DOMString) -> AttrValue {
+        match *local_name {
+            atom!("colspan") => AttrValue::from_u32(value, DEFAULT_COLSPAN),
+            atom!("rowspan") => AttrValue::from_u32(value, DEFAULT_ROWSPAN),
+            atom!("headers") => AttrValue::from_serialized_tokenlist(value),
+            _ => self.super_type().unwrap().parse_plain_attribute(local_name, value),
+        }
+    }
+}
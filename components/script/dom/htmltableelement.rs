@@ -7,25 +7,80 @@ use dom::attr::{Attr, AttrValue};
 use dom::bindings::codegen::Bindings::HTMLTableElementBinding;
 use dom::bindings::codegen::Bindings::HTMLTableElementBinding::HTMLTableElementMethods;
 use dom::bindings::codegen::Bindings::NodeBinding::NodeMethods;
+use dom::bindings::error::{Error, ErrorResult, Fallible};
 use dom::bindings::inheritance::Castable;
 use dom::bindings::js::{LayoutJS, Root, RootedReference};
 use dom::document::Document;
 use dom::element::{AttributeMutation, Element, RawLayoutElementHelpers};
+use dom::htmlcollection::{CollectionFilter, HTMLCollection};
 use dom::htmlelement::HTMLElement;
 use dom::htmltablecaptionelement::HTMLTableCaptionElement;
+use dom::htmltablerowelement::HTMLTableRowElement;
 use dom::htmltablesectionelement::HTMLTableSectionElement;
-use dom::node::{Node, document_from_node};
+use dom::node::{Node, document_from_node, window_from_node};
 use dom::virtualmethods::VirtualMethods;
 use std::cell::Cell;
 use string_cache::Atom;
 use util::str::{self, DOMString, LengthOrPercentageOrAuto};
 
+// https://html.spec.whatwg.org/multipage/#attr-table-frame
+#[derive(Clone, Copy, PartialEq, HeapSizeOf, JSTraceable)]
+pub enum TableFrame {
+    Void,
+    Above,
+    Below,
+    HSides,
+    VSides,
+    LHS,
+    RHS,
+    Box,
+    Border,
+}
+
+// https://html.spec.whatwg.org/multipage/#attr-table-rules
+#[derive(Clone, Copy, PartialEq, HeapSizeOf, JSTraceable)]
+pub enum TableRules {
+    None,
+    Groups,
+    Rows,
+    Cols,
+    All,
+}
+
+fn parse_table_frame(value: &str) -> Option<TableFrame> {
+    match &*value.to_lowercase() {
+        "void" => Some(TableFrame::Void),
+        "above" => Some(TableFrame::Above),
+        "below" => Some(TableFrame::Below),
+        "hsides" => Some(TableFrame::HSides),
+        "vsides" => Some(TableFrame::VSides),
+        "lhs" => Some(TableFrame::LHS),
+        "rhs" => Some(TableFrame::RHS),
+        "box" => Some(TableFrame::Box),
+        "border" => Some(TableFrame::Border),
+        _ => None,
+    }
+}
+
+fn parse_table_rules(value: &str) -> Option<TableRules> {
+    match &*value.to_lowercase() {
+        "none" => Some(TableRules::None),
+        "groups" => Some(TableRules::Groups),
+        "rows" => Some(TableRules::Rows),
+        "cols" => Some(TableRules::Cols),
+        "all" => Some(TableRules::All),
+        _ => None,
+    }
+}
+
 #[dom_struct]
 pub struct HTMLTableElement {
     htmlelement: HTMLElement,
     background_color: Cell<Option<RGBA>>,
     border: Cell<Option<u32>>,
     cellspacing: Cell<Option<u32>>,
+    frame: Cell<Option<TableFrame>>,
+    rules: Cell<Option<TableRules>>,
 }
 
 impl HTMLTableElement {
@@ -36,6 +91,8 @@ impl HTMLTableElement {
             background_color: Cell::new(None),
             border: Cell::new(None),
             cellspacing: Cell::new(None),
+            frame: Cell::new(None),
+            rules: Cell::new(None),
         }
     }
 
@@ -49,6 +106,46 @@ impl HTMLTableElement {
     pub fn get_border(&self) -> Option<u32> {
         self.border.get()
     }
+
+    fn get_first_section_of_type(&self, atom: &Atom) -> Option<Root<HTMLTableSectionElement>> {
+        self.upcast::<Node>()
+            .children()
+            .filter_map(Root::downcast::<HTMLTableSectionElement>)
+            .find(|n| n.upcast::<Element>().local_name() == atom)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#attr-table-frame
+    //
+    // A non-zero `border` with no explicit `frame` implies `frame=border`.
+    fn resolved_frame(&self) -> Option<TableFrame> {
+        self.frame.get().or_else(|| {
+            self.border.get().and_then(|border| {
+                if border != 0 { Some(TableFrame::Border) } else { None }
+            })
+        })
+    }
+
+    // https://html.spec.whatwg.org/multipage/#attr-table-rules
+    //
+    // A non-zero `border` with no explicit `rules` implies `rules=all`.
+    fn resolved_rules(&self) -> Option<TableRules> {
+        self.rules.get().or_else(|| {
+            self.border.get().and_then(|border| {
+                if border != 0 { Some(TableRules::All) } else { None }
+            })
+        })
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/#dom-table-thead
+//
+// `thead` is inserted before the first child that is neither a `caption`
+// nor a `colgroup`.
+fn is_caption_or_colgroup(child: &Node) -> bool {
+    match child.downcast::<Element>() {
+        Some(elem) => elem.is::<HTMLTableCaptionElement>() || elem.local_name() == &atom!("colgroup"),
+        None => false,
+    }
 }
 
 impl HTMLTableElementMethods for HTMLTableElement {
@@ -110,6 +207,223 @@ impl HTMLTableElementMethods for HTMLTableElement {
         tbody
     }
 
+    // https://html.spec.whatwg.org/multipage/#dom-table-thead
+    fn GetTHead(&self) -> Option<Root<HTMLTableSectionElement>> {
+        self.get_first_section_of_type(&atom!("thead"))
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-thead
+    fn SetTHead(&self, new_thead: Option<&HTMLTableSectionElement>) -> ErrorResult {
+        if let Some(thead) = new_thead {
+            if thead.upcast::<Element>().local_name() != &atom!("thead") {
+                return Err(Error::HierarchyRequest);
+            }
+        }
+
+        self.DeleteTHead();
+
+        if let Some(thead) = new_thead {
+            let node = self.upcast::<Node>();
+            let reference_node = node.children().find(|child| !is_caption_or_colgroup(child));
+
+            node.InsertBefore(thead.upcast(), reference_node.r()).expect("Insertion failed");
+        }
+        Ok(())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-createthead
+    fn CreateTHead(&self) -> Root<HTMLTableSectionElement> {
+        match self.GetTHead() {
+            Some(thead) => thead,
+            None => {
+                let thead = HTMLTableSectionElement::new(atom!("thead"),
+                                                         None,
+                                                         document_from_node(self).r());
+                self.SetTHead(Some(&thead)).expect("Setting THead failed");
+                thead
+            },
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-deletethead
+    fn DeleteTHead(&self) {
+        if let Some(thead) = self.GetTHead() {
+            thead.upcast::<Node>().remove_self();
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-tfoot
+    fn GetTFoot(&self) -> Option<Root<HTMLTableSectionElement>> {
+        self.get_first_section_of_type(&atom!("tfoot"))
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-tfoot
+    fn SetTFoot(&self, new_tfoot: Option<&HTMLTableSectionElement>) -> ErrorResult {
+        if let Some(tfoot) = new_tfoot {
+            if tfoot.upcast::<Element>().local_name() != &atom!("tfoot") {
+                return Err(Error::HierarchyRequest);
+            }
+        }
+
+        self.DeleteTFoot();
+
+        if let Some(tfoot) = new_tfoot {
+            let node = self.upcast::<Node>();
+            node.AppendChild(tfoot.upcast()).expect("Insertion failed");
+        }
+        Ok(())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-createtfoot
+    fn CreateTFoot(&self) -> Root<HTMLTableSectionElement> {
+        match self.GetTFoot() {
+            Some(tfoot) => tfoot,
+            None => {
+                let tfoot = HTMLTableSectionElement::new(atom!("tfoot"),
+                                                         None,
+                                                         document_from_node(self).r());
+                self.SetTFoot(Some(&tfoot)).expect("Setting TFoot failed");
+                tfoot
+            },
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-deletetfoot
+    fn DeleteTFoot(&self) {
+        if let Some(tfoot) = self.GetTFoot() {
+            tfoot.upcast::<Node>().remove_self();
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-rows
+    fn Rows(&self) -> Root<HTMLCollection> {
+        #[derive(JSTraceable, HeapSizeOf)]
+        struct TableRowFilter;
+        impl CollectionFilter for TableRowFilter {
+            fn filter(&self, elem: &Element, root: &Node) -> bool {
+                if !elem.is::<HTMLTableRowElement>() {
+                    return false;
+                }
+                let parent = match elem.upcast::<Node>().GetParentNode() {
+                    Some(parent) => parent,
+                    None => return false,
+                };
+
+                if parent.r() == root {
+                    return true;
+                }
+
+                let parent = match parent.downcast::<Element>() {
+                    Some(parent) => parent,
+                    None => return false,
+                };
+
+                parent.is::<HTMLTableSectionElement>() &&
+                (parent.local_name() == &atom!("thead") ||
+                 parent.local_name() == &atom!("tbody") ||
+                 parent.local_name() == &atom!("tfoot")) &&
+                parent.upcast::<Node>().GetParentNode().r() == Some(root)
+            }
+        }
+        HTMLCollection::create(&window_from_node(self), self.upcast(), box TableRowFilter)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-tbodies
+    fn TBodies(&self) -> Root<HTMLCollection> {
+        #[derive(JSTraceable, HeapSizeOf)]
+        struct TBodiesFilter;
+        impl CollectionFilter for TBodiesFilter {
+            fn filter(&self, elem: &Element, root: &Node) -> bool {
+                elem.is::<HTMLTableSectionElement>() &&
+                elem.local_name() == &atom!("tbody") &&
+                elem.upcast::<Node>().GetParentNode().r() == Some(root)
+            }
+        }
+        HTMLCollection::create(&window_from_node(self), self.upcast(), box TBodiesFilter)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-insertrow
+    fn InsertRow(&self, index: i32) -> Fallible<Root<HTMLElement>> {
+        let rows = self.Rows();
+        let number_of_row_elements = rows.Length();
+
+        if index < -1 || index > number_of_row_elements as i32 {
+            return Err(Error::IndexSize);
+        }
+
+        let new_row = HTMLTableRowElement::new(atom!("tr"), None, document_from_node(self).r());
+        let node = self.upcast::<Node>();
+
+        if number_of_row_elements == 0 {
+            // Append a new row to the last tbody, creating one if necessary.
+            let last_tbody =
+                node.rev_children()
+                    .filter_map(Root::downcast::<Element>)
+                    .find(|n| n.is::<HTMLTableSectionElement>() && n.local_name() == &atom!("tbody"));
+            match last_tbody {
+                Some(last_tbody) => {
+                    last_tbody.upcast::<Node>()
+                              .AppendChild(new_row.upcast::<Node>())
+                              .expect("InsertRow failed to append first row.");
+                },
+                None => {
+                    let tbody = self.CreateTBody();
+                    node.AppendChild(tbody.upcast::<Node>()).expect("InsertRow failed to append new tbody.");
+
+                    tbody.upcast::<Node>()
+                         .AppendChild(new_row.upcast::<Node>())
+                         .expect("InsertRow failed to append first row.");
+                },
+            }
+        } else if index == number_of_row_elements as i32 || index == -1 {
+            // Append the new row to the parent of the last row in the table.
+            let last_row = rows.Item(number_of_row_elements - 1)
+                                .expect("InsertRow failed to find last row in table.");
+
+            let last_row_parent = last_row.upcast::<Node>()
+                                           .GetParentNode()
+                                           .expect("InsertRow failed to find parent of last row in table.");
+
+            last_row_parent.AppendChild(new_row.upcast::<Node>())
+                            .expect("InsertRow failed to append last row.");
+        } else {
+            // Insert the new row before the index-th row, using the same parent.
+            let ith_row = rows.Item(index as u32)
+                              .expect("InsertRow failed to find a row in the collection");
+
+            let ith_row_parent = ith_row.upcast::<Node>()
+                                        .GetParentNode()
+                                        .expect("InsertRow failed to find parent of a row in table.");
+
+            ith_row_parent.InsertBefore(new_row.upcast::<Node>(), Some(ith_row.upcast::<Node>()))
+                           .expect("InsertRow failed to insert row before ith row.");
+        }
+
+        Ok(Root::upcast(new_row))
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-table-deleterow
+    fn DeleteRow(&self, mut index: i32) -> ErrorResult {
+        let rows = self.Rows();
+        let num_rows = rows.Length() as i32;
+
+        if index == -1 {
+            index = num_rows - 1;
+            if index == -1 {
+                // Table has no rows; nothing to do.
+                return Ok(());
+            }
+        }
+
+        if index < 0 || index >= num_rows {
+            return Err(Error::IndexSize);
+        }
+
+        let row = rows.Item(index as u32).expect("DeleteRow index out of bounds");
+        row.upcast::<Node>().remove_self();
+        Ok(())
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-table-bgcolor
     make_getter!(BgColor, "bgcolor");
 
@@ -123,21 +437,30 @@ impl HTMLTableElementMethods for HTMLTableElement {
     make_nonzero_dimension_setter!(SetWidth, "width");
 }
 
+// A legacy presentational HTML attribute, re-expressed as a CSS declaration.
+#[derive(Clone)]
+pub enum PresentationalHint {
+    BackgroundColor(RGBA),
+    BorderWidth(u32),
+    BorderSpacing(u32),
+    BorderStyle(TableFrame, TableRules),
+    Width(LengthOrPercentageOrAuto),
+    Padding(LengthOrPercentageOrAuto),
+}
+
+pub trait PresentationalHintsSynthesizer {
+    fn collect_presentational_hints(&self, hints: &mut Vec<PresentationalHint>);
+}
+
 pub trait HTMLTableElementLayoutHelpers {
-    fn get_background_color(&self) -> Option<RGBA>;
     fn get_border(&self) -> Option<u32>;
     fn get_cellspacing(&self) -> Option<u32>;
     fn get_width(&self) -> LengthOrPercentageOrAuto;
+    fn get_frame(&self) -> Option<TableFrame>;
+    fn get_rules(&self) -> Option<TableRules>;
 }
 
 impl HTMLTableElementLayoutHelpers for LayoutJS<HTMLTableElement> {
-    #[allow(unsafe_code)]
-    fn get_background_color(&self) -> Option<RGBA> {
-        unsafe {
-            (*self.unsafe_get()).background_color.get()
-        }
-    }
-
     #[allow(unsafe_code)]
     fn get_border(&self) -> Option<u32> {
         unsafe {
@@ -162,6 +485,62 @@ impl HTMLTableElementLayoutHelpers for LayoutJS<HTMLTableElement> {
                 .unwrap_or(LengthOrPercentageOrAuto::Auto)
         }
     }
+
+    // https://html.spec.whatwg.org/multipage/#attr-table-frame
+    //
+    // A non-zero `border` with no explicit `frame` implies `frame=border`.
+    #[allow(unsafe_code)]
+    fn get_frame(&self) -> Option<TableFrame> {
+        unsafe {
+            let this = &*self.unsafe_get();
+            this.frame.get().or_else(|| {
+                this.border.get().and_then(|border| {
+                    if border != 0 { Some(TableFrame::Border) } else { None }
+                })
+            })
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#attr-table-rules
+    //
+    // A non-zero `border` with no explicit `rules` implies `rules=all`.
+    #[allow(unsafe_code)]
+    fn get_rules(&self) -> Option<TableRules> {
+        unsafe {
+            let this = &*self.unsafe_get();
+            this.rules.get().or_else(|| {
+                this.border.get().and_then(|border| {
+                    if border != 0 { Some(TableRules::All) } else { None }
+                })
+            })
+        }
+    }
+}
+
+impl PresentationalHintsSynthesizer for HTMLTableElement {
+    fn collect_presentational_hints(&self, hints: &mut Vec<PresentationalHint>) {
+        if let Some(color) = self.background_color.get() {
+            hints.push(PresentationalHint::BackgroundColor(color));
+        }
+
+        if let Some(border) = self.border.get() {
+            if border != 0 {
+                hints.push(PresentationalHint::BorderWidth(border));
+            }
+        }
+
+        if let Some(cellspacing) = self.cellspacing.get() {
+            hints.push(PresentationalHint::BorderSpacing(cellspacing));
+        }
+
+        if let Some(frame) = self.resolved_frame() {
+            hints.push(PresentationalHint::BorderStyle(frame, self.resolved_rules().unwrap_or(TableRules::None)));
+        }
+
+        if let Some(width) = self.upcast::<Element>().get_attr(&ns!(), &atom!("width")) {
+            hints.push(PresentationalHint::Width(width.value().as_dimension().clone()));
+        }
+    }
 }
 
 impl VirtualMethods for HTMLTableElement {
@@ -188,6 +567,16 @@ impl VirtualMethods for HTMLTableElement {
                     str::parse_unsigned_integer(value.chars())
                 }));
             },
+            atom!("frame") => {
+                self.frame.set(mutation.new_value(attr).and_then(|value| {
+                    parse_table_frame(&value)
+                }));
+            },
+            atom!("rules") => {
+                self.rules.set(mutation.new_value(attr).and_then(|value| {
+                    parse_table_rules(&value)
+                }));
+            },
             _ => {},
         }
     }
@@ -196,6 +585,7 @@ impl VirtualMethods for HTMLTableElement {
         match *local_name {
             atom!("border") => AttrValue::from_u32(value, 1),
             atom!("width") => AttrValue::from_nonzero_dimension(value),
+            atom!("cellpadding") => AttrValue::from_dimension(value),
             _ => self.super_type().unwrap().parse_plain_attribute(local_name, value),
         }
     }